@@ -0,0 +1,19 @@
+/// Fixed loop period used to construct every attitude-controller PID, in seconds.
+pub const ATTITUDE_LOOP_TIME_SECS: f32 = 0.0025;
+
+/// Per-motor DShot command fired on the auxiliary/parachute-cut output when
+/// flight termination latches. `None` disables the aux output and the
+/// governor only holds minimum throttle.
+pub const TERMINATION_AUX_OUTPUT: Option<[i16; 4]> = None;
+
+/// Proportional gain of the optical-flow outer velocity loop (`PositionHold`).
+pub const FLOW_VEL_KP: f32 = 0.15;
+
+/// Integral gain of the optical-flow outer velocity loop (`PositionHold`).
+pub const FLOW_VEL_KI: f32 = 0.02;
+
+/// Minimum flow quality (`0.0..=1.0`) below which `PositionHold` degrades to `Horizon`.
+pub const FLOW_QUALITY_MIN: f32 = 0.3;
+
+/// Maximum roll/pitch angle [rad] the optical-flow velocity loop may command.
+pub const FLOW_TILT_MAX: f32 = 0.35;
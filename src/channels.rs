@@ -0,0 +1,55 @@
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::pubsub::{PubSubChannel, Publisher, Subscriber};
+use nalgebra::Vector3;
+
+use crate::task_attitude_controller::{FlowVelocity, StabilizationMode};
+
+/// Attitude angle (roll, pitch, yaw) and rate (roll, pitch, yaw), as produced
+/// by the sensor-fusion stack.
+pub type AttitudeSense = (Vector3<f32>, Vector3<f32>);
+
+// 2 subscribers: `attitude_controller` and `flow_position_estimator` each
+// need their own independent view of the latest attitude sense.
+static ATTITUDE_SENSE: PubSubChannel<CriticalSectionRawMutex, AttitudeSense, 1, 2, 1> = PubSubChannel::new();
+pub type AttitudeSenseSub = Subscriber<'static, CriticalSectionRawMutex, AttitudeSense, 1, 2, 1>;
+
+static ATTITUDE_INT_RESET: PubSubChannel<CriticalSectionRawMutex, bool, 1, 1, 1> = PubSubChannel::new();
+pub type AttitudeIntResetSub = Subscriber<'static, CriticalSectionRawMutex, bool, 1, 1, 1>;
+
+static ATTITUDE_STAB_MODE: PubSubChannel<CriticalSectionRawMutex, StabilizationMode, 1, 1, 1> = PubSubChannel::new();
+pub type AttitudeStabModeSub = Subscriber<'static, CriticalSectionRawMutex, StabilizationMode, 1, 1, 1>;
+
+static ATTITUDE_ACTUATE: PubSubChannel<CriticalSectionRawMutex, Vector3<f32>, 1, 1, 1> = PubSubChannel::new();
+pub type AttitudeActuatePub = Publisher<'static, CriticalSectionRawMutex, Vector3<f32>, 1, 1, 1>;
+
+/// A single downward optical-flow reading: pixel rate about the body x/y axes
+/// and a normalised quality figure, straight off the flow sensor driver.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RawFlow {
+    pub rate: Vector3<f32>,
+    pub quality: f32,
+}
+
+static RAW_FLOW: PubSubChannel<CriticalSectionRawMutex, RawFlow, 1, 1, 1> = PubSubChannel::new();
+pub type RawFlowSub = Subscriber<'static, CriticalSectionRawMutex, RawFlow, 1, 1, 1>;
+
+static HEIGHT: PubSubChannel<CriticalSectionRawMutex, f32, 1, 1, 1> = PubSubChannel::new();
+pub type HeightSub = Subscriber<'static, CriticalSectionRawMutex, f32, 1, 1, 1>;
+
+static FLOW_VELOCITY: PubSubChannel<CriticalSectionRawMutex, FlowVelocity, 1, 1, 1> = PubSubChannel::new();
+pub type FlowVelocitySub = Subscriber<'static, CriticalSectionRawMutex, FlowVelocity, 1, 1, 1>;
+pub type FlowVelocityPub = Publisher<'static, CriticalSectionRawMutex, FlowVelocity, 1, 1, 1>;
+
+/// Drain a subscriber without blocking, overwriting `value` with the most
+/// recent message if one is pending, leaving `value` untouched otherwise.
+pub fn update_from_channel<M, T, const CAP: usize, const SUBS: usize, const PUBS: usize>(
+    sub: &mut Subscriber<'static, M, T, CAP, SUBS, PUBS>,
+    value: &mut T,
+) where
+    M: embassy_sync::blocking_mutex::raw::RawMutex,
+    T: Clone,
+{
+    while let Some(new_value) = sub.try_next_message_pure() {
+        *value = new_value;
+    }
+}
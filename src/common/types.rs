@@ -0,0 +1,42 @@
+/// Reason the motors were, or are being, disarmed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum DisarmReason {
+    /// No disarm has happened yet - this is the state immediately after boot.
+    NotInitialized,
+    /// An arm-blocker condition was found raised at the end of the arming sequence.
+    Fault,
+    /// The vehicle was explicitly commanded to disarm.
+    Commanded,
+    /// While armed, no message (motor speed, arm-blocker, flight termination or
+    /// safety switch) arrived before the governor's timeout elapsed.
+    Timeout,
+    /// The physical safety switch was (re-)engaged.
+    SafetyEngaged,
+}
+
+/// State of an armed motor output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum ArmedState {
+    /// Armed, but commanded to zero throttle on all motors.
+    Idle,
+    /// Armed and spinning at the given per-motor speeds.
+    Running([i16; 4]),
+}
+
+/// State reported by the motor governor, consumed by e.g. [`status_led`](crate::t_motor_governor::status_led).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum MotorState {
+    /// Not armed, for the given reason.
+    Disarmed(DisarmReason),
+    /// Spinning up the ESCs and setting motor direction ahead of arming.
+    Arming,
+    /// Armed, in the given sub-state.
+    Armed(ArmedState),
+    /// Flight termination has latched - motors held at minimum throttle until
+    /// reboot. Deliberately a top-level variant rather than a
+    /// `Disarmed(DisarmReason)` case, so it can never be confused with, or
+    /// fall back to, a recoverable disarm.
+    Terminated,
+    /// Gated safe by the physical safety switch, regardless of `ArmBlocker`.
+    Safe,
+}
@@ -0,0 +1,75 @@
+use core::cell::Cell;
+
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::pubsub::{PubSubChannel, Subscriber};
+use embassy_sync::watch::{Receiver, Watch};
+use nalgebra::Vector3;
+
+use crate::common::types::MotorState;
+use crate::t_calibration::CalibrationRequest;
+use crate::t_motor_governor::{ArmBlocker, SafetyState, Severity};
+
+/// Raised arm-blocker bits. Empty means the vehicle is ready to arm.
+pub static ARM_BLOCKER: Watch<CriticalSectionRawMutex, ArmBlocker, 2> = Watch::new();
+
+/// Backing store for [`set_arm_blocker`], guarded by a critical section so
+/// independent tasks raising/clearing different bits (boot-grace, the
+/// calibration task, the RX-failsafe monitor, ...) can't lose an update to a
+/// concurrent read-modify-write.
+static ARM_BLOCKER_BITS: Mutex<CriticalSectionRawMutex, Cell<ArmBlocker>> =
+    Mutex::new(Cell::new(ArmBlocker::empty()));
+
+/// Atomically set or clear `bits` on the shared [`ARM_BLOCKER`] state and
+/// publish the result. This is the single entry point for mutating the flag -
+/// callers must not read-modify-write `ARM_BLOCKER` directly, since that
+/// races against every other task updating a different bit.
+pub fn set_arm_blocker(bits: ArmBlocker, raise: bool) {
+    let flag = ARM_BLOCKER_BITS.lock(|cell| {
+        let mut flag = cell.get();
+        flag.set(bits, raise);
+        cell.set(flag);
+        flag
+    });
+    ARM_BLOCKER.sender().send(flag);
+}
+
+/// Commanded per-motor DShot speeds, consumed by `motor_governor` while armed.
+pub static MOTOR_SPEEDS: Watch<CriticalSectionRawMutex, [i16; 4], 1> = Watch::new();
+
+/// Current motor state, as reported by `motor_governor`.
+pub static MOTOR_STATE: Watch<CriticalSectionRawMutex, MotorState, 1> = Watch::new();
+
+/// Human-readable, severity-tagged arm-blocker lines, one per raised bit,
+/// emitted by `arm_blocker_reporter` whenever the raised set changes. A
+/// queued `PubSubChannel` rather than a single-slot `Watch`: more than one
+/// bit is commonly raised at once (e.g. right after boot), and a `Watch`
+/// would let each `send` overwrite the previous one before a consumer gets
+/// to read it. Capacity comfortably covers every `ArmBlocker` bit changing
+/// at once.
+pub static ARM_BLOCKER_TEXT: PubSubChannel<CriticalSectionRawMutex, (Severity, &'static str), 16, 1, 1> =
+    PubSubChannel::new();
+pub type ArmBlockerTextSub = Subscriber<'static, CriticalSectionRawMutex, (Severity, &'static str), 16, 1, 1>;
+
+/// Trigger for the latching flight-termination kill path. The payload carries
+/// no information beyond "terminate now" - the reason is logged by whichever
+/// task raises it (geofence breach, failsafe, excess attitude, ...).
+pub static FLIGHT_TERMINATION: Watch<CriticalSectionRawMutex, (), 1> = Watch::new();
+
+/// State of the physical safety switch, gating motor output above the
+/// `ArmBlocker` flow regardless of whether every bit is clear.
+pub static SAFETY_STATE: Watch<CriticalSectionRawMutex, SafetyState, 2> = Watch::new();
+
+/// Request to (re-)calibrate one sensor, consumed by the `calibration` task.
+pub static CALIBRATE: Watch<CriticalSectionRawMutex, CalibrationRequest, 1> = Watch::new();
+
+/// Raw (gyro, accel) IMU sample, sampled by the `calibration` task while a
+/// calibration is in progress.
+pub static RAW_IMU: Watch<CriticalSectionRawMutex, (Vector3<f32>, Vector3<f32>), 1> = Watch::new();
+pub type RawImuReceiver = Receiver<'static, CriticalSectionRawMutex, (Vector3<f32>, Vector3<f32>), 1>;
+
+/// Gyroscope bias offset produced by the `calibration` task.
+pub static GYR_CALIBRATION: Watch<CriticalSectionRawMutex, Vector3<f32>, 1> = Watch::new();
+
+/// Accelerometer bias offset produced by the `calibration` task.
+pub static ACC_CALIBRATION: Watch<CriticalSectionRawMutex, Vector3<f32>, 1> = Watch::new();
@@ -0,0 +1,149 @@
+use nalgebra::Vector3;
+use embassy_time::{with_timeout, Duration, Instant, Timer};
+
+use crate::t_motor_governor::ArmBlocker;
+use crate::messaging as msg;
+
+pub const TASK_ID: &str = "[CALIBRATION]";
+
+/// Number of leading samples discarded to flush sensor transients before
+/// accumulation begins, following ArduPilot's `update_calibration` discipline.
+const DISCARD_SAMPLES: usize = 5;
+
+/// Minimum number of healthy samples that must be accumulated before a result
+/// is accepted.
+const MIN_SAMPLES: usize = 15;
+
+/// Minimum wall-clock window the accumulated samples must span, so a burst of
+/// fast readings cannot satisfy `MIN_SAMPLES` without observing the sensor over
+/// a meaningful interval.
+const MIN_WINDOW: Duration = Duration::from_secs(1);
+
+/// Per-sample timeout - if a sensor stops producing data mid-calibration the
+/// attempt is aborted rather than blocking forever.
+const SAMPLE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Maximum per-axis variance [sensor units squared] tolerated during a
+/// calibration. Exceeding it means the vehicle moved and the result is rejected.
+const MOTION_VARIANCE_MAX: f32 = 0.02;
+
+/// Which sensor a calibration request targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum CalibrationRequest {
+    Gyroscope,
+    Accelerometer,
+}
+
+/// Task that produces gyroscope and accelerometer bias offsets and keeps the
+/// [`ArmBlocker`] calibration bits consistent with the result.
+///
+/// On request it raises the matching `*_CALIBIN` bit, discards the first few
+/// readings to flush transients, then accumulates at least [`MIN_SAMPLES`]
+/// healthy samples spanning at least [`MIN_WINDOW`]. The bias is the sample
+/// mean; if the per-axis variance exceeds [`MOTION_VARIANCE_MAX`] (the vehicle
+/// moved) or too few healthy samples arrive, the attempt is aborted. On success
+/// it stores the offset and clears both the `*_CALIBIN` and `NO_*_CALIB` bits;
+/// on failure it leaves `NO_*_CALIB` set so the arming interlock stays honest.
+#[embassy_executor::task]
+pub async fn calibration() -> ! {
+
+    // Input messages
+    let mut rcv_request = msg::CALIBRATE.receiver().unwrap();
+    let mut rcv_imu = msg::RAW_IMU.receiver().unwrap();
+
+    // Output messages
+    let snd_gyr_calib = msg::GYR_CALIBRATION.sender();
+    let snd_acc_calib = msg::ACC_CALIBRATION.sender();
+
+    loop {
+        let request = rcv_request.changed().await;
+
+        let (calibin_bit, no_calib_bit) = match request {
+            CalibrationRequest::Gyroscope     => (ArmBlocker::GYR_CALIBIN, ArmBlocker::NO_GYR_CALIB),
+            CalibrationRequest::Accelerometer => (ArmBlocker::ACC_CALIBIN, ArmBlocker::NO_ACC_CALIB),
+        };
+
+        defmt::info!("{} : Starting {} calibration", TASK_ID, request);
+        msg::set_arm_blocker(calibin_bit, true);
+
+        // Sample the axis of interest: the gyro rate, or the accelerometer
+        // reading with gravity removed along the (assumed level) z axis.
+        let result = collect(&mut rcv_imu, request).await;
+
+        match result {
+            Ok(offset) => {
+                match request {
+                    CalibrationRequest::Gyroscope     => snd_gyr_calib.send(offset),
+                    CalibrationRequest::Accelerometer => snd_acc_calib.send(offset),
+                }
+                // Clear both the in-progress and the not-calibrated bits.
+                msg::set_arm_blocker(calibin_bit | no_calib_bit, false);
+                defmt::info!("{} : sensor calibrated ({})", TASK_ID, request);
+            }
+            Err(()) => {
+                // Leave NO_*_CALIB set, only drop the in-progress bit.
+                msg::set_arm_blocker(calibin_bit, false);
+                defmt::warn!("{} : sensor unhealthy ({})", TASK_ID, request);
+            }
+        }
+    }
+}
+
+/// Accumulate samples for one calibration, returning the mean offset on success
+/// or `Err(())` if the sensor was unhealthy or motion was detected.
+async fn collect(
+    rcv_imu: &mut msg::RawImuReceiver,
+    request: CalibrationRequest,
+) -> Result<Vector3<f32>, ()> {
+
+    let extract = |gyro: Vector3<f32>, accel: Vector3<f32>| match request {
+        CalibrationRequest::Gyroscope     => gyro,
+        // Remove the expected 1 g along z, leaving the accelerometer bias.
+        CalibrationRequest::Accelerometer => accel - Vector3::new(0., 0., 9.81),
+    };
+
+    // Discard the first few readings to flush transients.
+    for _ in 0..DISCARD_SAMPLES {
+        if with_timeout(SAMPLE_TIMEOUT, rcv_imu.changed()).await.is_err() {
+            return Err(());
+        }
+    }
+
+    let start = Instant::now();
+    let mut count: usize = 0;
+    let mut sum = Vector3::zeros();
+    let mut sum_sq = Vector3::zeros();
+
+    // Accumulate until both the sample-count and the wall-clock window are met.
+    while count < MIN_SAMPLES || start.elapsed() < MIN_WINDOW {
+        let Ok((gyro, accel)) = with_timeout(SAMPLE_TIMEOUT, rcv_imu.changed()).await else {
+            return Err(());
+        };
+        let sample = extract(gyro, accel);
+        sum += sample;
+        sum_sq += sample.component_mul(&sample);
+        count += 1;
+    }
+
+    if count < MIN_SAMPLES {
+        return Err(());
+    }
+
+    let n = count as f32;
+    let mean = sum / n;
+
+    // Reject the result if any axis moved more than the motion threshold.
+    let variance = sum_sq / n - mean.component_mul(&mean);
+    if variance.x > MOTION_VARIANCE_MAX
+        || variance.y > MOTION_VARIANCE_MAX
+        || variance.z > MOTION_VARIANCE_MAX
+    {
+        return Err(());
+    }
+
+    // Brief settle after the last sample before the caller clears the
+    // in-progress bit, so a request issued immediately after this returns
+    // doesn't race the final accumulation.
+    Timer::after_millis(10).await;
+    Ok(mean)
+}
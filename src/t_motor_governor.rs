@@ -1,6 +1,7 @@
 use crate::common::types::{ArmedState, DisarmReason, MotorState};
 use crate::drivers::rp2040::dshot_pio::{DshotPio, DshotPioTrait};
-use embassy_futures::select::{select, Either};
+use embassy_futures::select::{select, select4, Either, Either4};
+use embassy_rp::gpio::{Input, Level, Output};
 use embassy_rp::peripherals::PIO0;
 use embassy_time::{with_timeout, Duration, Timer};
 
@@ -56,8 +57,102 @@ bitflags::bitflags! {
     }
 }
 
+/// Severity of an arming-prevention message, mirroring the MAVLink
+/// `MAV_SEVERITY` levels so the same text can be routed to a GCS link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum Severity {
+    /// Informational, the condition is expected to clear on its own.
+    Info,
+    /// The condition requires attention but may still clear automatically.
+    Warning,
+    /// The condition requires operator action before the vehicle can arm.
+    Error,
+}
+
+impl ArmBlocker {
+    /// Describe each currently raised bit as a severity-tagged, human-readable
+    /// line. The text is prefixed with `"PreArm: "` following the ArduPilot
+    /// convention so operators recognise it as an arming interlock, and the
+    /// iterator yields nothing when the flag is empty (ready to arm).
+    pub fn describe(self) -> impl Iterator<Item = (Severity, &'static str)> {
+        self.iter().map(|bit| match bit {
+            ArmBlocker::NO_GYR_CALIB     => (Severity::Error,   "PreArm: Gyroscope not calibrated"),
+            ArmBlocker::NO_ACC_CALIB     => (Severity::Error,   "PreArm: Accelerometer not calibrated"),
+            ArmBlocker::GYR_CALIBIN      => (Severity::Info,    "PreArm: Gyroscope calibration in progress"),
+            ArmBlocker::ACC_CALIBIN      => (Severity::Info,    "PreArm: Accelerometer calibration in progress"),
+            ArmBlocker::NO_GYR_DATA      => (Severity::Error,   "PreArm: No gyroscope data"),
+            ArmBlocker::NO_ACC_DATA      => (Severity::Error,   "PreArm: No accelerometer data"),
+            ArmBlocker::HIGH_THROTTLE_CMD => (Severity::Warning, "PreArm: Throttle above arming limit"),
+            ArmBlocker::HIGH_ATTITUDE_CMD => (Severity::Warning, "PreArm: Attitude command above arming limit"),
+            ArmBlocker::HIGH_ATTITUDE    => (Severity::Error,   "PreArm: Vehicle attitude angle too high"),
+            ArmBlocker::BOOT_GRACE       => (Severity::Info,    "PreArm: Waiting for boot grace period"),
+            ArmBlocker::SYSTEM_LOAD      => (Severity::Warning, "PreArm: System load too high"),
+            ArmBlocker::RX_FAILSAFE      => (Severity::Error,   "PreArm: Receiver in failsafe"),
+            ArmBlocker::CMD_DISARM       => (Severity::Info,    "PreArm: Disarm commanded"),
+            _                            => (Severity::Warning, "PreArm: Unknown arming condition"),
+        })
+    }
+}
+
+/// State of the physical safety switch, kept deliberately separate from the
+/// software [`ArmBlocker`] checks. Modeled on PX4 splitting its `safety` topic
+/// out of `actuator_armed`: the switch gates motor output independently of the
+/// arming logic, so even a fully-cleared `ArmBlocker` cannot spin the motors
+/// while the switch is engaged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum SafetyState {
+    /// Switch engaged - outputs held safe regardless of the arm-blocker state.
+    Engaged,
+    /// Switch disengaged - the arm-blocker flow is allowed to proceed.
+    Disengaged,
+}
+
 use crate::messaging as msg;
 
+const REPORTER_TASK_ID: &str = "[ARM BLOCKER REPORTER]";
+const SAFETY_TASK_ID: &str = "[SAFETY SWITCH]";
+
+/// Task to translate the raw [`ArmBlocker`] bitflag into human-readable,
+/// severity-tagged lines. Whenever the set of raised bits changes it emits one
+/// line per active bit on [`msg::ARM_BLOCKER_TEXT`] and to the defmt log, so the
+/// operator learns *which* check is holding the vehicle disarmed instead of
+/// facing a silent refusal to arm.
+#[embassy_executor::task]
+pub async fn arm_blocker_reporter() -> ! {
+
+    // Input messages
+    let mut rcv_arming_prevention = msg::ARM_BLOCKER.receiver().unwrap();
+
+    // Output messages
+    let snd_arm_blocker_text = msg::ARM_BLOCKER_TEXT.publisher().unwrap();
+
+    // `None` until the first flag is observed, so a boot-time value that
+    // happens to equal `ArmBlocker::all()` is never mistaken for the sentinel
+    // and silently dropped.
+    let mut previous: Option<ArmBlocker> = None;
+
+    loop {
+        let flag = rcv_arming_prevention.changed().await;
+        if previous == Some(flag) {
+            continue;
+        }
+        previous = Some(flag);
+
+        for (severity, text) in flag.describe() {
+            match severity {
+                Severity::Info    => defmt::info!("{} : {}", REPORTER_TASK_ID, text),
+                Severity::Warning => defmt::warn!("{} : {}", REPORTER_TASK_ID, text),
+                Severity::Error   => defmt::error!("{} : {}", REPORTER_TASK_ID, text),
+            }
+            // Queued publish (not `publish_immediate`) so a burst of several
+            // bits changing at once - the common case right after boot -
+            // cannot have an earlier line overwritten before a consumer
+            // reads it.
+            snd_arm_blocker_text.publish((severity, text)).await;
+        }
+    }
+}
+
 /// Task to govern the arming, disarming and setting the speed of the motors.
 /// Arming takes 3.5 seconds: 3.0 s to arm, 0.5 s to set direction
 #[embassy_executor::task]
@@ -74,30 +169,72 @@ pub async fn motor_governor(
     // Output messages
     let snd_motor_state = msg::MOTOR_STATE.sender();
 
+    // Flight-termination trigger
+    let mut rcv_flight_termination = msg::FLIGHT_TERMINATION.receiver().unwrap();
+
+    // Hardware safety switch, gating motor output above the arm-blocker flow
+    let mut rcv_safety_state = msg::SAFETY_STATE.receiver().unwrap();
+
     // Send initial disarmed state
     snd_motor_state.send(MotorState::Disarmed(DisarmReason::NotInitialized));
 
     #[allow(unused_labels)]
     'infinite: loop {
+        // Safety-switch gate, sitting *above* the arm-blocker flow. While the
+        // switch is engaged the governor stays in a pre-arm "safe" posture -
+        // holding minimum throttle and reporting MotorState::Safe - regardless
+        // of whether every ArmBlocker bit is clear.
+        if rcv_safety_state.get().await == SafetyState::Engaged {
+            defmt::info!("{} : Safety switch engaged -> safe", TASK_ID);
+            snd_motor_state.send(MotorState::Safe);
+            out_dshot_pio.throttle_minimum();
+            rcv_safety_state.changed_and(|s| *s == SafetyState::Disengaged).await;
+        }
+
         // Wait for arming prevention flag to be completely empty
         rcv_arming_prevention.changed_and(|flag| flag.is_empty()).await;
 
         // Notify that motors are arming
         snd_motor_state.send(MotorState::Arming);
 
-        // Send minimum throttle for a few seconds to arm the ESCs
+        // Send minimum throttle for a few seconds to arm the ESCs, then set
+        // motor direction. The safety switch is raced against every tick of
+        // this ~3.5 s sequence (not just polled before and after it) so
+        // re-engaging it mid-spin-up disarms immediately instead of being
+        // silently ignored until `'armed` is reached.
+        let mut safety_reengaged = false;
+        let safety_disarm = |s: &SafetyState| *s == SafetyState::Engaged;
+
         defmt::info!("{} : Initializing motors", TASK_ID);
-        Timer::after_millis(500).await;
+        match select(Timer::after_millis(500), rcv_safety_state.changed_and(safety_disarm)).await {
+            Either::First(_) => {}
+            Either::Second(_) => safety_reengaged = true,
+        }
         for _i in 0..50 {
+            if safety_reengaged { break; }
             out_dshot_pio.throttle_minimum();
-            Timer::after_millis(50).await;
+            match select(Timer::after_millis(50), rcv_safety_state.changed_and(safety_disarm)).await {
+                Either::First(_) => {}
+                Either::Second(_) => safety_reengaged = true,
+            }
         }
 
-        // Set motor directions for the four motors
-        defmt::info!("{} : Setting motor directions", TASK_ID);
-        for _i in 0..10 {
-            out_dshot_pio.reverse(reverse_motor);
-            Timer::after_millis(50).await;
+        if !safety_reengaged {
+            defmt::info!("{} : Setting motor directions", TASK_ID);
+            for _i in 0..10 {
+                out_dshot_pio.reverse(reverse_motor);
+                match select(Timer::after_millis(50), rcv_safety_state.changed_and(safety_disarm)).await {
+                    Either::First(_) => {}
+                    Either::Second(_) => { safety_reengaged = true; break; }
+                }
+            }
+        }
+
+        if safety_reengaged {
+            defmt::warn!("{} : Disarming motors -> safety engaged", TASK_ID);
+            out_dshot_pio.throttle_minimum();
+            snd_motor_state.send(MotorState::Disarmed(DisarmReason::SafetyEngaged));
+            continue 'infinite;
         }
 
         // After arming, ensure (again) no arming prevention flags are set
@@ -112,32 +249,64 @@ pub async fn motor_governor(
             
             match with_timeout(
                 timeout,
-                select(
+                select4(
                     rcv_motor_speed.changed(),
                     rcv_arming_prevention.changed(),
+                    rcv_flight_termination.changed(),
+                    rcv_safety_state.changed(),
                 )
             ).await {
 
                 // Motors are set to idle (armed, not spinning)
-                Ok(Either::First([0,0,0,0])) => {
+                Ok(Either4::First([0,0,0,0])) => {
                     out_dshot_pio.throttle_minimum();
                     snd_motor_state.send(MotorState::Armed(ArmedState::Idle));
                 },
 
                 // Motor speed message received correctly
-                Ok(Either::First(speeds)) => {
+                Ok(Either4::First(speeds)) => {
                     out_dshot_pio.throttle_clamp(speeds);
                     snd_motor_state.send(MotorState::Armed(ArmedState::Running(speeds)));
                 },
 
+                // Safety switch re-engaged while armed -> immediate disarm
+                Ok(Either4::Fourth(SafetyState::Engaged)) => {
+                    defmt::warn!("{} : Disarming motors -> safety engaged", TASK_ID);
+                    out_dshot_pio.throttle_minimum();
+                    snd_motor_state.send(MotorState::Disarmed(DisarmReason::SafetyEngaged));
+                    break 'armed;
+                },
+
                 // Motors are commanded to disarm
-                Ok(Either::Second(flag)) if flag.contains(ArmBlocker::CMD_DISARM) => {
+                Ok(Either4::Second(flag)) if flag.contains(ArmBlocker::CMD_DISARM) => {
                     defmt::warn!("{} : Disarming motors -> commanded", TASK_ID);
                     out_dshot_pio.throttle_minimum();
                     snd_motor_state.send(MotorState::Disarmed(DisarmReason::Commanded));
                     break 'armed;
                 },
 
+                // Flight termination commanded - irreversible kill path.
+                // Unlike an ordinary disarm this latches: the motors are held at
+                // minimum throttle, the auxiliary cut output is fired, and the
+                // task parks forever so that no empty ARM_BLOCKER can re-arm the
+                // vehicle. A reboot is required to clear this state.
+                Ok(Either4::Third(_)) => {
+                    defmt::error!("{} : Flight termination -> latching", TASK_ID);
+                    out_dshot_pio.throttle_minimum();
+                    snd_motor_state.send(MotorState::Terminated);
+                    if let Some(aux) = crate::cfg::TERMINATION_AUX_OUTPUT {
+                        // Hold the aux command for a deliberate duration so the
+                        // parachute/cut signal is actually observable on the
+                        // output before the governor parks at minimum throttle.
+                        out_dshot_pio.throttle_clamp(aux);
+                        Timer::after_millis(500).await;
+                    }
+                    loop {
+                        out_dshot_pio.throttle_minimum();
+                        Timer::after_millis(50).await;
+                    }
+                },
+
                 // Automatic disarm due to message timeout
                 Err(_) => {
                     defmt:: warn!("{} : Disarming motors -> timeout", TASK_ID);
@@ -150,4 +319,71 @@ pub async fn motor_governor(
             }
         }
     }
-}
\ No newline at end of file
+}
+/// Debounced input task for the physical safety button. The button is active-low
+/// with an internal pull-up; a level is only accepted once it has been stable for
+/// `DEBOUNCE` so switch bounce and EMI cannot toggle the arming gate. Each
+/// accepted transition is published on [`msg::SAFETY_STATE`].
+#[embassy_executor::task]
+pub async fn safety_switch(mut button: Input<'static>) -> ! {
+    const DEBOUNCE: Duration = Duration::from_millis(50);
+
+    let snd_safety_state = msg::SAFETY_STATE.sender();
+
+    // Active-low: pressed (Low) means the switch is engaged.
+    let level_to_state = |level: Level| match level {
+        Level::Low => SafetyState::Engaged,
+        Level::High => SafetyState::Disengaged,
+    };
+
+    let mut state = level_to_state(button.get_level());
+    snd_safety_state.send(state);
+
+    loop {
+        button.wait_for_any_edge().await;
+
+        // Require the new level to persist for the debounce window.
+        Timer::after(DEBOUNCE).await;
+        let debounced = level_to_state(button.get_level());
+        if debounced != state {
+            state = debounced;
+            defmt::info!("{} : {}", SAFETY_TASK_ID, state);
+            snd_safety_state.send(state);
+        }
+    }
+}
+
+/// Drive a status LED with a distinct blink pattern per motor state so the
+/// armed / safe / blocked states are distinguishable on the bench:
+/// - solid on while armed,
+/// - slow heartbeat while held safe by the switch,
+/// - fast blink while disarmed (an arm-blocker is raised),
+/// - off once flight termination has latched.
+#[embassy_executor::task]
+pub async fn status_led(mut led: Output<'static>) -> ! {
+    let mut rcv_motor_state = msg::MOTOR_STATE.receiver().unwrap();
+    let mut state = rcv_motor_state.get().await;
+
+    loop {
+        let (on, off) = match state {
+            MotorState::Armed(_) | MotorState::Arming => (Duration::from_millis(1000), Duration::from_millis(0)),
+            MotorState::Safe => (Duration::from_millis(100), Duration::from_millis(900)),
+            MotorState::Disarmed(_) => (Duration::from_millis(100), Duration::from_millis(100)),
+            MotorState::Terminated => (Duration::from_millis(0), Duration::from_millis(1000)),
+        };
+
+        if on.as_ticks() != 0 {
+            led.set_high();
+        }
+        match select(Timer::after(on), rcv_motor_state.changed()).await {
+            Either::First(_) => {}
+            Either::Second(new_state) => { state = new_state; continue; }
+        }
+
+        led.set_low();
+        match select(Timer::after(off), rcv_motor_state.changed()).await {
+            Either::First(_) => {}
+            Either::Second(new_state) => { state = new_state; }
+        }
+    }
+}
@@ -10,7 +10,13 @@ use defmt::*;
 #[derive(Clone, Debug, Copy, PartialEq)]
 pub enum StabilizationMode {
     Horizon(Vector3<f32>),
-    Acro(Vector3<f32>)
+    Acro(Vector3<f32>),
+
+    /// Optical-flow position hold. The reference is a commanded body-frame
+    /// horizontal velocity (x, y) with a yaw-angle reference in z. An outer
+    /// velocity loop converts the velocity error into roll/pitch *angle*
+    /// references which then feed the existing Horizon angle → rate cascade.
+    PositionHold(Vector3<f32>),
 }
 
 impl Format for StabilizationMode {
@@ -28,23 +34,42 @@ impl StabilizationMode {
         match self {
             StabilizationMode::Horizon(_) => 0,
             StabilizationMode::Acro(_) => 1,
+            StabilizationMode::PositionHold(_) => 2,
         }
     }
 }
 
+/// Body-frame horizontal velocity produced by the optical-flow estimator,
+/// together with a normalised `quality` in `0..=1` used to decide whether the
+/// estimate is trustworthy enough for position hold.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct FlowVelocity {
+    /// Forward body-frame velocity [m/s].
+    pub x: f32,
+    /// Right body-frame velocity [m/s].
+    pub y: f32,
+    /// Flow quality, `0.0` (unusable) to `1.0` (ideal).
+    pub quality: f32,
+}
+
 static TASK_ID : &str = "ATTITUDE_CONTROLLER";
+static FLOW_TASK_ID : &str = "FLOW_ESTIMATOR";
 
 #[embassy_executor::task]
 pub async fn attitude_controller(
     mut s_attitude_sense: channels::AttitudeSenseSub,
     mut s_attitude_int_reset : channels::AttitudeIntResetSub,
     mut s_attitude_stab_mode : channels::AttitudeStabModeSub,
+    mut s_flow_velocity : channels::FlowVelocitySub,
     p_attitude_actuate: channels::AttitudeActuatePub,
 ) {
 
     // Aquire satbilization mode
     let mut stabilization_mode = s_attitude_stab_mode.next_message_pure().await;
 
+    // Latest optical-flow velocity estimate (body-frame x/y m/s + quality 0..1)
+    let mut flow_velocity = FlowVelocity::default();
+
     // Setup controllers for pitch, roll and yaw, using a cascaded controller scheme.
     let mut pid_pitch_outer = Pid::new( 10., 0.1, 0., true, cfg::ATTITUDE_LOOP_TIME_SECS );
     let mut pid_pitch_inner = Pid::new( 40., 1.0, 0.01, true, cfg::ATTITUDE_LOOP_TIME_SECS ).set_lp_filter(0.01);
@@ -53,6 +78,12 @@ pub async fn attitude_controller(
     let mut pid_yaw_outer = Pid::new( 8., 0.001, 0., true, cfg::ATTITUDE_LOOP_TIME_SECS ).set_circular(-PI, PI);
     let mut pid_yaw_inner = Pid::new( 60., 1.0, 0., true, cfg::ATTITUDE_LOOP_TIME_SECS ).set_circular(-PI, PI).set_lp_filter(0.01);
 
+    // Outer velocity loop for optical-flow position hold. Each axis converts a
+    // body-frame velocity error into a roll/pitch angle reference, clamped to
+    // the tilt limit so the craft cannot command an aggressive attitude.
+    let mut pid_vel_x = Pid::new( cfg::FLOW_VEL_KP, cfg::FLOW_VEL_KI, 0., true, cfg::ATTITUDE_LOOP_TIME_SECS ).set_lp_filter(0.05);
+    let mut pid_vel_y = Pid::new( cfg::FLOW_VEL_KP, cfg::FLOW_VEL_KI, 0., true, cfg::ATTITUDE_LOOP_TIME_SECS ).set_lp_filter(0.05);
+
     info!("{} : Entering main loop",TASK_ID);
     loop {
 
@@ -64,8 +95,12 @@ pub async fn attitude_controller(
             pid_pitch_outer.reset_integral();   pid_pitch_inner.reset_integral();
             pid_roll_outer.reset_integral();    pid_roll_inner.reset_integral();
             pid_yaw_outer.reset_integral();     pid_yaw_inner.reset_integral();
+            pid_vel_x.reset_integral();         pid_vel_y.reset_integral();
         }
 
+        // Update the optical-flow velocity estimate if a new sample is ready
+        crate::channels::update_from_channel(&mut s_flow_velocity, &mut flow_velocity);
+
         // Wait for new measurements to arrive
         let (att_angle,att_rate) = s_attitude_sense.next_message_pure().await;
 
@@ -100,8 +135,93 @@ pub async fn attitude_controller(
                     pid_yaw_inner.update( error.z )
                 )
             }
+
+            StabilizationMode::PositionHold(reference) => {
+
+                // Degrade gracefully to Horizon (level, hold heading) when the
+                // flow estimate is not trustworthy enough to hold position.
+                if flow_velocity.quality < cfg::FLOW_QUALITY_MIN {
+                    warn!("{} : Flow quality {} below threshold -> Horizon", TASK_ID, flow_velocity.quality);
+                    stabilization_mode = StabilizationMode::Horizon(Vector3::new(0., 0., reference.z));
+                    continue;
+                }
+
+                // Outer velocity loop: commanded velocity (x, y) minus the
+                // flow-derived body velocity yields a roll/pitch angle reference.
+                // A positive x (forward) velocity error commands a nose-down
+                // (negative pitch) attitude, hence the sign on the pitch axis.
+                let vel_error_x = reference.x - flow_velocity.x;
+                let vel_error_y = reference.y - flow_velocity.y;
+                let angle_reference = Vector3::new(
+                    pid_vel_y.update( vel_error_y ).clamp(-cfg::FLOW_TILT_MAX, cfg::FLOW_TILT_MAX),
+                    -pid_vel_x.update( vel_error_x ).clamp(-cfg::FLOW_TILT_MAX, cfg::FLOW_TILT_MAX),
+                    reference.z,
+                );
+
+                // Run the existing Horizon angle → rate cascade on the derived
+                // angle reference, leaving yaw and the inner loops untouched.
+                let outer_error = angle_reference - att_angle;
+                let inner_reference = Vector3::new(
+                    pid_roll_outer.update( outer_error.x ),
+                    pid_pitch_outer.update( outer_error.y ),
+                    pid_yaw_outer.update( outer_error.z )
+                );
+
+                let inner_error = inner_reference - att_rate;
+                Vector3::new(
+                    pid_roll_inner.update( inner_error.x ),
+                    pid_pitch_inner.update( inner_error.y ),
+                    pid_yaw_inner.update( inner_error.z )
+                )
+            }
         });
     }
 }
 
 
+
+/// Optical-flow velocity estimator, following the structure of PX4's
+/// `flow_position_estimator`. Downward optical-flow pixel rates are compensated
+/// for the body rotation measured by the gyro and scaled by the height estimate
+/// to recover a body-frame horizontal velocity:
+///
+/// `velocity ≈ (flow_rate − body_rate) × height`
+///
+/// The estimate is published on [`channels::FlowVelocityPub`] for the attitude
+/// controller's `PositionHold` mode to consume. Flow quality is passed through
+/// so the controller can fall back to `Horizon` when the surface texture or
+/// lighting is poor.
+#[embassy_executor::task]
+pub async fn flow_position_estimator(
+    mut s_raw_flow: channels::RawFlowSub,
+    mut s_attitude_sense: channels::AttitudeSenseSub,
+    mut s_height: channels::HeightSub,
+    p_flow_velocity: channels::FlowVelocityPub,
+) {
+    let mut height = 0f32;
+
+    info!("{} : Entering main loop", FLOW_TASK_ID);
+    loop {
+
+        // Keep the latest height estimate without blocking on it.
+        crate::channels::update_from_channel(&mut s_height, &mut height);
+
+        // A raw flow sample drives the estimator cadence.
+        let raw = s_raw_flow.next_message_pure().await;
+
+        // Body rotation rate (roll, pitch) at the time of the flow sample.
+        let (_att_angle, att_rate) = s_attitude_sense.next_message_pure().await;
+
+        // Subtract the rotation-induced flow, then scale by height. The flow
+        // pixel rate about the x axis corresponds to motion along y and vice
+        // versa, matching the downward-camera geometry.
+        let vx = (raw.rate.y - att_rate.y) * height;
+        let vy = (raw.rate.x - att_rate.x) * height;
+
+        p_flow_velocity.publish_immediate(FlowVelocity {
+            x: vx,
+            y: vy,
+            quality: raw.quality,
+        });
+    }
+}